@@ -0,0 +1,166 @@
+use anyhow::{Context, Result};
+use rcgen::{
+    BasicConstraints, Certificate, CertificateParams, DistinguishedName, DnType,
+    ExtendedKeyUsagePurpose, IsCa, KeyUsagePurpose, SanType,
+};
+use std::fs;
+use std::net::Ipv4Addr;
+use std::path::Path;
+use time::OffsetDateTime;
+
+/// A generated certificate and its private key, both PEM-encoded.
+pub struct GeneratedCertificate {
+    pub certificate_pem: String,
+    pub key_pem: String,
+}
+
+impl GeneratedCertificate {
+    /// Writes the certificate and key to the given PEM file paths.
+    pub fn write_to_files(&self, certificate_file: &Path, key_file: &Path) -> Result<()> {
+        fs::write(certificate_file, &self.certificate_pem)
+            .with_context(|| format!("writing certificate to {certificate_file:?}"))?;
+        fs::write(key_file, &self.key_pem)
+            .with_context(|| format!("writing private key to {key_file:?}"))?;
+
+        Ok(())
+    }
+}
+
+/// Generates a self-signed tunnel CA.
+pub fn generate_ca(
+    common_name: &str,
+    not_before: OffsetDateTime,
+    not_after: OffsetDateTime,
+) -> Result<(Certificate, GeneratedCertificate)> {
+    let mut params = CertificateParams::default();
+    params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+    params.not_before = not_before;
+    params.not_after = not_after;
+    params.distinguished_name = common_name_dn(common_name);
+
+    let ca = Certificate::from_params(params).context("generating tunnel CA")?;
+    let certificate_pem = ca
+        .serialize_pem()
+        .context("serializing tunnel CA certificate")?;
+    let key_pem = ca.serialize_private_key_pem();
+
+    Ok((
+        ca,
+        GeneratedCertificate {
+            certificate_pem,
+            key_pem,
+        },
+    ))
+}
+
+/// Generates a server leaf certificate signed by `ca`, valid for a tunnel's bind and tunnel addresses.
+pub fn generate_server_certificate(
+    ca: &Certificate,
+    address_tunnel: Ipv4Addr,
+    bind_address: Ipv4Addr,
+    not_before: OffsetDateTime,
+    not_after: OffsetDateTime,
+) -> Result<GeneratedCertificate> {
+    let mut params = CertificateParams::default();
+    params.not_before = not_before;
+    params.not_after = not_after;
+    params.subject_alt_names = vec![
+        SanType::IpAddress(address_tunnel.into()),
+        SanType::IpAddress(bind_address.into()),
+    ];
+    params.distinguished_name = common_name_dn("quincy-server");
+
+    sign_leaf(params, ca).context("generating server certificate")
+}
+
+/// Generates a client leaf certificate signed by `ca`, for use in mutual TLS.
+pub fn generate_client_certificate(
+    ca: &Certificate,
+    username: &str,
+    not_before: OffsetDateTime,
+    not_after: OffsetDateTime,
+) -> Result<GeneratedCertificate> {
+    let mut params = CertificateParams::default();
+    params.not_before = not_before;
+    params.not_after = not_after;
+    params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ClientAuth];
+    params.distinguished_name = common_name_dn(username);
+
+    sign_leaf(params, ca).context("generating client certificate")
+}
+
+fn sign_leaf(params: CertificateParams, ca: &Certificate) -> Result<GeneratedCertificate> {
+    let leaf = Certificate::from_params(params)?;
+    let certificate_pem = leaf.serialize_pem_with_signer(ca)?;
+    let key_pem = leaf.serialize_private_key_pem();
+
+    Ok(GeneratedCertificate {
+        certificate_pem,
+        key_pem,
+    })
+}
+
+fn common_name_dn(common_name: &str) -> DistinguishedName {
+    let mut distinguished_name = DistinguishedName::new();
+    distinguished_name.push(DnType::CommonName, common_name);
+
+    distinguished_name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::certificates::{load_certificates_from_file, load_root_cert_store};
+    use std::net::Ipv4Addr;
+    use std::time::Duration as StdDuration;
+    use time::Duration;
+
+    fn validity_window() -> (OffsetDateTime, OffsetDateTime) {
+        let not_before = OffsetDateTime::UNIX_EPOCH + StdDuration::from_secs(1_700_000_000);
+        let not_after = not_before + Duration::days(365);
+
+        (not_before, not_after)
+    }
+
+    #[test]
+    fn generated_certificates_round_trip_through_the_loaders() {
+        let dir = tempfile::tempdir().unwrap();
+        let (not_before, not_after) = validity_window();
+        let (ca, ca_certificate) = generate_ca("test-ca", not_before, not_after).unwrap();
+
+        let ca_file = dir.path().join("ca.pem");
+        ca_certificate
+            .write_to_files(&ca_file, &dir.path().join("ca.key.pem"))
+            .unwrap();
+        load_root_cert_store(&ca_file).expect("generated CA loads as a root cert store");
+
+        let server_file = dir.path().join("server.pem");
+        generate_server_certificate(
+            &ca,
+            Ipv4Addr::new(10, 0, 0, 1),
+            Ipv4Addr::new(0, 0, 0, 0),
+            not_before,
+            not_after,
+        )
+        .unwrap()
+        .write_to_files(&server_file, &dir.path().join("server.key.pem"))
+        .unwrap();
+        load_certificates_from_file(&server_file).expect("generated server cert is valid PEM");
+    }
+
+    #[test]
+    fn client_certificate_round_trips_through_the_loader() {
+        let dir = tempfile::tempdir().unwrap();
+        let (not_before, not_after) = validity_window();
+        let (ca, _) = generate_ca("test-ca", not_before, not_after).unwrap();
+
+        let client_file = dir.path().join("client.pem");
+        generate_client_certificate(&ca, "alice", not_before, not_after)
+            .unwrap()
+            .write_to_files(&client_file, &dir.path().join("client.key.pem"))
+            .unwrap();
+
+        load_certificates_from_file(&client_file).expect("generated client cert is valid PEM");
+    }
+}
@@ -0,0 +1,63 @@
+use anyhow::Result;
+use clap::Args;
+use std::net::Ipv4Addr;
+use std::path::PathBuf;
+use time::{Duration, OffsetDateTime};
+
+use crate::certificates::{generate_ca, generate_client_certificate, generate_server_certificate};
+
+/// Arguments for the `certificates` CLI subcommand.
+#[derive(Debug, Args)]
+pub struct CertificatesArgs {
+    /// Directory to write the generated CA, server and client certificate/key files to
+    #[arg(long, default_value = "certificates")]
+    pub output_dir: PathBuf,
+    /// The tunnel's internal address, included as a server certificate SAN
+    #[arg(long)]
+    pub address_tunnel: Ipv4Addr,
+    /// The address the server binds to, included as a server certificate SAN
+    #[arg(long, default_value = "0.0.0.0")]
+    pub bind_address: Ipv4Addr,
+    /// The username to embed in the generated client certificate
+    #[arg(long, default_value = "client")]
+    pub client_username: String,
+    /// Validity period for the generated certificates, in days
+    #[arg(long, default_value_t = 365)]
+    pub validity_days: i64,
+}
+
+/// Runs the `certificates` CLI subcommand: generates a tunnel CA and a server and client
+/// leaf certificate signed by it, writing PEM files to `args.output_dir`.
+pub fn run(args: &CertificatesArgs) -> Result<()> {
+    std::fs::create_dir_all(&args.output_dir)?;
+
+    let not_before = OffsetDateTime::now_utc();
+    let not_after = not_before + Duration::days(args.validity_days);
+
+    let (ca, ca_certificate) = generate_ca("quincy-tunnel-ca", not_before, not_after)?;
+    ca_certificate.write_to_files(
+        &args.output_dir.join("ca.pem"),
+        &args.output_dir.join("ca.key.pem"),
+    )?;
+
+    let server_certificate = generate_server_certificate(
+        &ca,
+        args.address_tunnel,
+        args.bind_address,
+        not_before,
+        not_after,
+    )?;
+    server_certificate.write_to_files(
+        &args.output_dir.join("server.pem"),
+        &args.output_dir.join("server.key.pem"),
+    )?;
+
+    let client_certificate =
+        generate_client_certificate(&ca, &args.client_username, not_before, not_after)?;
+    client_certificate.write_to_files(
+        &args.output_dir.join("client.pem"),
+        &args.output_dir.join("client.key.pem"),
+    )?;
+
+    Ok(())
+}
@@ -4,6 +4,8 @@ use figment::{
     Figment,
 };
 use quinn::{MtuDiscoveryConfig, TransportConfig, VarInt};
+use rustls::client::{ClientSessionMemoryCache, StoresClientSessions};
+use rustls::server::AllowAnyAuthenticatedClient;
 use rustls::{Certificate, RootCertStore};
 use serde::de::DeserializeOwned;
 use serde::Deserialize;
@@ -12,11 +14,15 @@ use std::collections::HashMap;
 use std::net::Ipv4Addr;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::constants::{
     QUIC_MTU_OVERHEAD, QUINCY_CIPHER_SUITES, TLS_ALPN_PROTOCOLS, TLS_PROTOCOL_VERSIONS,
 };
-use crate::utils::certificates::{load_certificates_from_file, load_private_key_from_file};
+use crate::utils::certificates::{
+    load_certificates_from_file, load_private_key_from_file, load_root_cert_store,
+};
+use crate::utils::PersistentClientSessionCache;
 use tracing::{error, warn};
 
 /// Represents the configuration for a Quincy server.
@@ -55,6 +61,20 @@ pub struct TunnelConfig {
     #[serde(default = "default_auth_timeout")]
     /// The amount of time in seconds to wait for authentication before closing the connection
     pub auth_timeout: u32,
+    /// A path to the tunnel CA certificate used to authenticate client certificates
+    ///
+    /// When set, the server requires clients to present a certificate signed by this CA
+    /// before the QUIC handshake completes, in addition to the regular password flow.
+    pub ca_file: Option<PathBuf>,
+    /// Whether to request a UPnP/IGD port mapping for `bind_port` on the gateway
+    #[serde(default)]
+    pub upnp: bool,
+    /// The lease duration in seconds to request for the UPnP port mapping
+    #[serde(default = "default_upnp_lease_duration")]
+    pub upnp_lease_duration: u32,
+    /// Whether to issue TLS session tickets so that clients can resume via 0-RTT
+    #[serde(default)]
+    pub resumption: bool,
 }
 
 /// Represents the configuration for a Quincy client.
@@ -82,6 +102,29 @@ pub struct ClientAuthenticationConfig {
     /// The interval at which to send the session token
     #[serde(default = "default_auth_timeout")]
     pub auth_interval: u32,
+    /// The client certificate to present to the server for mutual TLS authentication
+    pub certificate_file: Option<PathBuf>,
+    /// The private key belonging to `certificate_file`
+    pub certificate_key_file: Option<PathBuf>,
+    /// Whether to cache TLS session tickets to speed up reconnects via resumption and 0-RTT
+    #[serde(default)]
+    pub resumption: bool,
+    /// An optional path to persist cached session tickets across client restarts
+    ///
+    /// When unset, the session cache is kept in memory only and is lost on restart.
+    pub session_cache_file: Option<PathBuf>,
+}
+
+/// Represents a selectable TLS/crypto backend for the Quinn endpoint.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CryptoProvider {
+    /// The default `ring`-backed rustls provider
+    #[default]
+    Rustls,
+    /// A BoringSSL-backed provider, useful for FIPS-friendly builds
+    #[cfg(feature = "boringssl")]
+    Boring,
 }
 
 /// Represents miscellaneous connection configuration.
@@ -89,6 +132,12 @@ pub struct ClientAuthenticationConfig {
 pub struct ConnectionConfig {
     /// The MTU to use for connections and the TUN interface
     pub mtu: u32,
+    /// The TLS/crypto backend to use for the Quinn endpoint
+    #[serde(default)]
+    pub crypto_provider: CryptoProvider,
+    /// Path MTU discovery (PLPMTUD) tuning
+    #[serde(default)]
+    pub mtu_discovery: MtuDiscoverySettings,
     /// The size of the send buffer of the socket and Quinn endpoint
     #[serde(default = "default_buffer_size")]
     pub send_buffer_size: u64,
@@ -97,6 +146,35 @@ pub struct ConnectionConfig {
     pub recv_buffer_size: u64,
 }
 
+/// Represents path MTU discovery (PLPMTUD) tuning for a Quinn transport.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct MtuDiscoverySettings {
+    /// Master switch for path MTU discovery; when `false`, a fixed MTU is used instead
+    #[serde(default = "default_enable_mtu_discovery")]
+    pub enabled: bool,
+    /// The initial UDP payload size assumed before any probing occurs
+    pub initial_max_udp_payload_size: Option<u16>,
+    /// The smallest MTU change, in bytes, worth probing for; the search for a larger MTU stops
+    /// once the remaining gap is below this
+    pub minimum_change: Option<u16>,
+    /// The largest MTU that probing will search up to
+    pub upper_bound: Option<u16>,
+    /// How often to re-probe after suspecting the path has black-holed larger packets, in seconds
+    pub black_hole_detection_interval: Option<u64>,
+}
+
+impl Default for MtuDiscoverySettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_enable_mtu_discovery(),
+            initial_max_udp_payload_size: None,
+            minimum_change: None,
+            upper_bound: None,
+            black_hole_detection_interval: None,
+        }
+    }
+}
+
 /// Represents logging configuration.
 #[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct LogConfig {
@@ -171,6 +249,9 @@ impl FromPath<ServerConfig> for ServerConfig {}
 impl FromPath<ClientConfig> for ClientConfig {}
 impl FromPath<TunnelConfig> for TunnelConfig {}
 
+/// The number of TLS session tickets kept in the in-memory resumption cache
+const RESUMPTION_CACHE_SIZE: usize = 256;
+
 fn default_log_level() -> String {
     "info".to_string()
 }
@@ -191,6 +272,14 @@ fn default_auth_timeout() -> u32 {
     120
 }
 
+fn default_upnp_lease_duration() -> u32 {
+    3600
+}
+
+fn default_enable_mtu_discovery() -> bool {
+    true
+}
+
 impl ClientConfig {
     /// Creates Quinn client configuration from this Quincy client configuration.
     ///
@@ -217,26 +306,67 @@ impl ClientConfig {
             cert_store.add(&certificate)?;
         }
 
-        let mut rustls_config = rustls::ClientConfig::builder()
-            .with_cipher_suites(QUINCY_CIPHER_SUITES)
-            .with_safe_default_kx_groups()
-            .with_protocol_versions(TLS_PROTOCOL_VERSIONS)?
-            .with_root_certificates(cert_store)
-            .with_no_client_auth();
+        let client_auth_cert = match (
+            &self.authentication.certificate_file,
+            &self.authentication.certificate_key_file,
+        ) {
+            (Some(certificate_file), Some(certificate_key_file)) => Some((
+                load_certificates_from_file(certificate_file)?,
+                load_private_key_from_file(certificate_key_file)?,
+            )),
+            _ => None,
+        };
 
-        rustls_config.alpn_protocols = TLS_ALPN_PROTOCOLS.clone();
+        let mut quinn_config = match self.connection.crypto_provider {
+            CryptoProvider::Rustls => {
+                let rustls_config_builder = rustls::ClientConfig::builder()
+                    .with_cipher_suites(QUINCY_CIPHER_SUITES)
+                    .with_safe_default_kx_groups()
+                    .with_protocol_versions(TLS_PROTOCOL_VERSIONS)?
+                    .with_root_certificates(cert_store);
+
+                let mut rustls_config = match client_auth_cert {
+                    Some((certs, key)) => {
+                        rustls_config_builder.with_client_auth_cert(certs, key)?
+                    }
+                    None => rustls_config_builder.with_no_client_auth(),
+                };
+
+                rustls_config.alpn_protocols = TLS_ALPN_PROTOCOLS.clone();
+
+                if self.authentication.resumption {
+                    let session_storage: Arc<dyn StoresClientSessions + Send + Sync> =
+                        match &self.authentication.session_cache_file {
+                            Some(session_cache_file) => {
+                                PersistentClientSessionCache::load(session_cache_file)
+                            }
+                            None => ClientSessionMemoryCache::new(RESUMPTION_CACHE_SIZE),
+                        };
+
+                    rustls_config.session_storage = session_storage;
+                    rustls_config.enable_early_data = true;
+                }
+
+                quinn::ClientConfig::new(Arc::new(rustls_config))
+            }
+            #[cfg(feature = "boringssl")]
+            CryptoProvider::Boring => {
+                crate::crypto::boring::client_config(cert_store, client_auth_cert)?
+            }
+        };
 
-        let mut quinn_config = quinn::ClientConfig::new(Arc::new(rustls_config));
         let mut transport_config = TransportConfig::default();
-        let mut mtu_config = MtuDiscoveryConfig::default();
 
         transport_config.max_idle_timeout(Some(
             VarInt::from_u32(self.authentication.auth_interval * 2 * 1_000).into(),
         ));
 
-        mtu_config.upper_bound(self.connection.mtu as u16 + QUIC_MTU_OVERHEAD);
+        apply_mtu_discovery(
+            &mut transport_config,
+            &self.connection.mtu_discovery,
+            self.connection.mtu,
+        );
 
-        transport_config.mtu_discovery_config(Some(mtu_config));
         quinn_config.transport_config(Arc::new(transport_config));
 
         Ok(quinn_config)
@@ -260,27 +390,122 @@ impl TunnelConfig {
         let key = load_private_key_from_file(&certificate_key_path)?;
         let certs = load_certificates_from_file(&certificate_file_path)?;
 
-        let mut rustls_config = rustls::ServerConfig::builder()
-            .with_cipher_suites(QUINCY_CIPHER_SUITES)
-            .with_safe_default_kx_groups()
-            .with_protocol_versions(TLS_PROTOCOL_VERSIONS)?
-            .with_no_client_auth()
-            .with_single_cert(certs, key)?;
+        let mut quinn_config = match connection_config.crypto_provider {
+            CryptoProvider::Rustls => {
+                let rustls_config_builder = rustls::ServerConfig::builder()
+                    .with_cipher_suites(QUINCY_CIPHER_SUITES)
+                    .with_safe_default_kx_groups()
+                    .with_protocol_versions(TLS_PROTOCOL_VERSIONS)?;
+
+                let mut rustls_config = match &self.ca_file {
+                    Some(ca_file) => {
+                        let client_cert_verifier =
+                            AllowAnyAuthenticatedClient::new(load_root_cert_store(ca_file)?);
+
+                        rustls_config_builder
+                            .with_client_cert_verifier(Arc::new(client_cert_verifier))
+                            .with_single_cert(certs, key)?
+                    }
+                    None => rustls_config_builder
+                        .with_no_client_auth()
+                        .with_single_cert(certs, key)?,
+                };
+
+                rustls_config.alpn_protocols = TLS_ALPN_PROTOCOLS.clone();
+
+                if self.resumption {
+                    rustls_config.ticketer = rustls::Ticketer::new()?;
+                    rustls_config.max_early_data_size = u32::MAX;
+                }
 
-        rustls_config.alpn_protocols = TLS_ALPN_PROTOCOLS.clone();
+                quinn::ServerConfig::with_crypto(Arc::new(rustls_config))
+            }
+            #[cfg(feature = "boringssl")]
+            CryptoProvider::Boring => {
+                crate::crypto::boring::server_config(certs, key, &self.ca_file)?
+            }
+        };
 
-        let mut quinn_config = quinn::ServerConfig::with_crypto(Arc::new(rustls_config));
         let mut transport_config = TransportConfig::default();
-        let mut mtu_config = MtuDiscoveryConfig::default();
 
         transport_config
             .max_idle_timeout(Some(VarInt::from_u32(self.auth_timeout * 2 * 1_000).into()));
 
-        mtu_config.upper_bound(connection_config.mtu as u16 + QUIC_MTU_OVERHEAD);
+        apply_mtu_discovery(
+            &mut transport_config,
+            &connection_config.mtu_discovery,
+            connection_config.mtu,
+        );
 
-        transport_config.mtu_discovery_config(Some(mtu_config));
         quinn_config.transport_config(Arc::new(transport_config));
 
         Ok(quinn_config)
     }
 }
+
+/// Applies path MTU discovery (PLPMTUD) settings to a Quinn transport configuration, pinning
+/// `initial_max_udp_payload_size` to the configured MTU instead when disabled.
+fn apply_mtu_discovery(
+    transport_config: &mut TransportConfig,
+    settings: &MtuDiscoverySettings,
+    mtu: u32,
+) {
+    if !settings.enabled {
+        transport_config.initial_max_udp_payload_size(mtu as u16);
+        transport_config.mtu_discovery_config(None);
+        return;
+    }
+
+    let mut mtu_config = MtuDiscoveryConfig::default();
+
+    mtu_config.upper_bound(
+        settings
+            .upper_bound
+            .unwrap_or(mtu as u16 + QUIC_MTU_OVERHEAD),
+    );
+
+    if let Some(minimum_change) = settings.minimum_change {
+        mtu_config.minimum_change(minimum_change);
+    }
+
+    if let Some(interval) = settings.black_hole_detection_interval {
+        mtu_config.black_hole_detection_interval(Duration::from_secs(interval));
+    }
+
+    if let Some(initial_max_udp_payload_size) = settings.initial_max_udp_payload_size {
+        transport_config.initial_max_udp_payload_size(initial_max_udp_payload_size);
+    }
+
+    transport_config.mtu_discovery_config(Some(mtu_config));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabling_mtu_discovery_clears_the_default_probe_config() {
+        let mut transport_config = TransportConfig::default();
+        let settings = MtuDiscoverySettings {
+            enabled: false,
+            ..MtuDiscoverySettings::default()
+        };
+
+        apply_mtu_discovery(&mut transport_config, &settings, 1400);
+
+        assert!(format!("{transport_config:?}").contains("mtu_discovery_config: None"));
+    }
+
+    #[test]
+    fn enabling_mtu_discovery_installs_a_probe_config() {
+        let mut transport_config = TransportConfig::default();
+        let settings = MtuDiscoverySettings {
+            enabled: true,
+            ..MtuDiscoverySettings::default()
+        };
+
+        apply_mtu_discovery(&mut transport_config, &settings, 1400);
+
+        assert!(format!("{transport_config:?}").contains("mtu_discovery_config: Some"));
+    }
+}
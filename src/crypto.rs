@@ -0,0 +1,32 @@
+#[cfg(feature = "boringssl")]
+pub mod boring {
+    use anyhow::Result;
+    use rustls::{Certificate, PrivateKey, RootCertStore};
+    use std::path::PathBuf;
+
+    use crate::utils::certificates::load_root_cert_store;
+
+    /// Builds a Quinn client configuration backed by BoringSSL.
+    pub fn client_config(
+        root_certs: RootCertStore,
+        client_auth_cert: Option<(Vec<Certificate>, PrivateKey)>,
+    ) -> Result<quinn::ClientConfig> {
+        let crypto_provider = quinn_boring::client_config(root_certs, client_auth_cert)?;
+
+        Ok(quinn::ClientConfig::new(crypto_provider))
+    }
+
+    /// Builds a Quinn server configuration backed by BoringSSL, trusting client certificates
+    /// signed by `ca_file` if given.
+    pub fn server_config(
+        certs: Vec<Certificate>,
+        key: PrivateKey,
+        ca_file: &Option<PathBuf>,
+    ) -> Result<quinn::ServerConfig> {
+        let client_cert_verifier = ca_file.as_ref().map(load_root_cert_store).transpose()?;
+
+        let crypto_provider = quinn_boring::server_config(certs, key, client_cert_verifier)?;
+
+        Ok(quinn::ServerConfig::with_crypto(crypto_provider))
+    }
+}
@@ -0,0 +1,47 @@
+use anyhow::Result;
+use std::net::{SocketAddr, SocketAddrV4};
+
+use crate::config::{ConnectionConfig, TunnelConfig};
+use crate::utils::{bind_socket, UpnpPortMapping};
+
+/// Everything produced by starting up a single tunnel: the bound socket, the Quinn server
+/// configuration to run on it, and a handle to auxiliary services (currently just UPnP)
+/// that must be torn down on shutdown.
+pub struct TunnelHandle {
+    pub socket: std::net::UdpSocket,
+    pub quinn_config: quinn::ServerConfig,
+    upnp_mapping: Option<UpnpPortMapping>,
+}
+
+impl TunnelHandle {
+    /// Starts a tunnel: binds its socket, builds its Quinn server configuration, and - if
+    /// `tunnel.upnp` is set - requests and maintains a UPnP port mapping for it.
+    pub async fn start(tunnel: &TunnelConfig, connection: &ConnectionConfig) -> Result<Self> {
+        let bind_address = SocketAddrV4::new(tunnel.bind_address, tunnel.bind_port);
+        let socket = bind_socket(
+            SocketAddr::V4(bind_address),
+            connection.send_buffer_size as usize,
+            connection.recv_buffer_size as usize,
+        )?;
+        let quinn_config = tunnel.as_quinn_server_config(connection)?;
+
+        let upnp_mapping = if tunnel.upnp {
+            Some(UpnpPortMapping::start(bind_address, tunnel.upnp_lease_duration).await)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            socket,
+            quinn_config,
+            upnp_mapping,
+        })
+    }
+
+    /// Shuts the tunnel down, releasing its UPnP port mapping if one was requested.
+    pub async fn shutdown(self) {
+        if let Some(upnp_mapping) = self.upnp_mapping {
+            upnp_mapping.shutdown().await;
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use anyhow::{Context, Result};
+use rustls::{Certificate, PrivateKey, RootCertStore};
+use rustls_pemfile::{certs, pkcs8_private_keys};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+/// Loads a chain of PEM-encoded certificates from a file.
+pub fn load_certificates_from_file(path: &Path) -> Result<Vec<Certificate>> {
+    let file = File::open(path).with_context(|| format!("opening certificate file {path:?}"))?;
+    let mut reader = BufReader::new(file);
+
+    Ok(certs(&mut reader)
+        .with_context(|| format!("parsing certificates from {path:?}"))?
+        .into_iter()
+        .map(Certificate)
+        .collect())
+}
+
+/// Loads a PKCS#8 private key from a file.
+pub fn load_private_key_from_file(path: &Path) -> Result<PrivateKey> {
+    let file = File::open(path).with_context(|| format!("opening private key file {path:?}"))?;
+    let mut reader = BufReader::new(file);
+
+    let key = pkcs8_private_keys(&mut reader)
+        .with_context(|| format!("parsing private key from {path:?}"))?
+        .into_iter()
+        .next()
+        .with_context(|| format!("no private key found in {path:?}"))?;
+
+    Ok(PrivateKey(key))
+}
+
+/// Loads a CA certificate chain from a file into a `RootCertStore`, for verifying peer
+/// certificates signed by that CA (e.g. a tunnel's mutual TLS CA).
+pub fn load_root_cert_store(path: &Path) -> Result<RootCertStore> {
+    let mut cert_store = RootCertStore::empty();
+
+    for certificate in load_certificates_from_file(path)? {
+        cert_store.add(&certificate)?;
+    }
+
+    Ok(cert_store)
+}
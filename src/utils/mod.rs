@@ -0,0 +1,270 @@
+pub mod certificates;
+
+use crate::constants::{BINCODE_BUFFER_SIZE, BINCODE_CONFIG};
+use anyhow::{Context, Result};
+use bincode::{Decode, Encode};
+use bytes::{Bytes, BytesMut};
+use igd::aio::tokio::search_gateway;
+use igd::PortMappingProtocol;
+use rustls::client::StoresClientSessions;
+use socket2::{Domain, Protocol, Socket, Type};
+use std::collections::HashMap;
+use std::fs;
+use std::net::{SocketAddr, SocketAddrV4};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{debug, error, warn};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::EnvFilter;
+
+pub fn bind_socket(
+    addr: SocketAddr,
+    send_buffer_size: usize,
+    recv_buffer_size: usize,
+) -> Result<std::net::UdpSocket> {
+    let socket = Socket::new(Domain::for_address(addr), Type::DGRAM, Some(Protocol::UDP))
+        .context("create socket")?;
+
+    if addr.is_ipv6() {
+        socket.set_only_v6(false).context("set_only_v6")?;
+    }
+
+    socket
+        .bind(&socket2::SockAddr::from(addr))
+        .context("binding endpoint")?;
+    socket
+        .set_send_buffer_size(send_buffer_size)
+        .context("send buffer size")?;
+    socket
+        .set_recv_buffer_size(recv_buffer_size)
+        .context("recv buffer size")?;
+
+    let buf_size = socket.send_buffer_size().context("send buffer size")?;
+    if buf_size < send_buffer_size {
+        warn!(
+            "Unable to set desired send buffer size. Desired: {}, Actual: {}",
+            send_buffer_size, buf_size
+        );
+    }
+
+    let buf_size = socket.recv_buffer_size().context("recv buffer size")?;
+    if buf_size < recv_buffer_size {
+        warn!(
+            "Unable to set desired recv buffer size. Desired: {}, Actual: {}",
+            recv_buffer_size, buf_size
+        );
+    }
+
+    Ok(socket.into())
+}
+
+/// Requests a UPnP/IGD UDP port mapping for the given bind address on the local gateway.
+///
+/// Failures are logged as warnings rather than returned, since a tunnel should still be
+/// able to start on networks without a UPnP-capable gateway.
+pub async fn add_upnp_port_mapping(bind_address: SocketAddrV4, lease_duration: u32) {
+    let gateway = match search_gateway(Default::default()).await {
+        Ok(gateway) => gateway,
+        Err(e) => {
+            warn!("Could not discover a UPnP gateway: {e}");
+            return;
+        }
+    };
+
+    if let Err(e) = gateway
+        .add_port(
+            PortMappingProtocol::UDP,
+            bind_address.port(),
+            bind_address,
+            lease_duration,
+            "Quincy tunnel",
+        )
+        .await
+    {
+        warn!("Could not add a UPnP port mapping for {bind_address}: {e}");
+    }
+}
+
+/// Removes a previously requested UPnP/IGD UDP port mapping.
+pub async fn remove_upnp_port_mapping(external_port: u16) {
+    let gateway = match search_gateway(Default::default()).await {
+        Ok(gateway) => gateway,
+        Err(e) => {
+            warn!("Could not discover a UPnP gateway while releasing the port mapping: {e}");
+            return;
+        }
+    };
+
+    if let Err(e) = gateway
+        .remove_port(PortMappingProtocol::UDP, external_port)
+        .await
+    {
+        error!("Could not release the UPnP port mapping for port {external_port}: {e}");
+    }
+}
+
+/// A handle to a UPnP/IGD port mapping that is kept alive for as long as the handle lives,
+/// via a background task that renews the lease before it expires.
+///
+/// Dropping the handle stops the renewal task, but does not release the mapping itself
+/// (releasing requires an async call to the gateway) - call [`UpnpPortMapping::shutdown`]
+/// during tunnel shutdown to release it.
+pub struct UpnpPortMapping {
+    bind_address: SocketAddrV4,
+    renewal_task: JoinHandle<()>,
+}
+
+impl Drop for UpnpPortMapping {
+    fn drop(&mut self) {
+        self.renewal_task.abort();
+    }
+}
+
+/// The shortest interval allowed between lease renewals, regardless of the configured lease
+/// duration, so a very short `upnp_lease_duration` can't spin the renewal loop.
+const MIN_RENEWAL_INTERVAL: Duration = Duration::from_secs(5);
+
+impl UpnpPortMapping {
+    /// Requests a UPnP/IGD port mapping for `bind_address` and spawns a background task
+    /// that renews the lease at half the lease duration (floored at
+    /// [`MIN_RENEWAL_INTERVAL`]), for as long as the returned handle is held.
+    pub async fn start(bind_address: SocketAddrV4, lease_duration: u32) -> Self {
+        add_upnp_port_mapping(bind_address, lease_duration).await;
+
+        let renewal_interval =
+            Duration::from_secs(lease_duration as u64 / 2).max(MIN_RENEWAL_INTERVAL);
+        let renewal_task = tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(renewal_interval).await;
+
+                debug!("Renewing UPnP port mapping for {bind_address}");
+                add_upnp_port_mapping(bind_address, lease_duration).await;
+            }
+        });
+
+        Self {
+            bind_address,
+            renewal_task,
+        }
+    }
+
+    /// Stops renewing the lease and releases the port mapping from the gateway.
+    pub async fn shutdown(self) {
+        self.renewal_task.abort();
+        remove_upnp_port_mapping(self.bind_address.port()).await;
+    }
+}
+
+/// Completes a client connection attempt, logging whether it resumed via 0-RTT or required
+/// a full TLS handshake.
+///
+/// This is the client-side complement to [`crate::config::ClientAuthenticationConfig::resumption`]
+/// - it lets operators verify from the logs that resumption is actually taking effect on
+/// reconnects, rather than just that it is configured.
+pub async fn connect_with_resumption_tracing(
+    connecting: quinn::Connecting,
+) -> Result<quinn::Connection, quinn::ConnectionError> {
+    match connecting.into_0rtt() {
+        Ok((connection, zero_rtt_accepted)) => {
+            if zero_rtt_accepted.await {
+                debug!("Reconnected via 0-RTT (TLS session resumed)");
+            } else {
+                debug!("0-RTT data was rejected by the server, fell back to a full handshake");
+            }
+
+            Ok(connection)
+        }
+        Err(connecting) => {
+            debug!("Performing a full TLS handshake (no 0-RTT)");
+            connecting.await
+        }
+    }
+}
+
+pub fn enable_tracing(log_level: &str) {
+    let registry = tracing_subscriber::Registry::default();
+    let fmt_layer = tracing_subscriber::fmt::Layer::new();
+    let filter_layer = EnvFilter::try_new(log_level).unwrap();
+
+    let subscriber = registry.with(filter_layer).with(fmt_layer);
+    tracing::subscriber::set_global_default(subscriber).unwrap();
+}
+
+pub fn encode_message<M: Encode>(message: M) -> Result<Bytes> {
+    let mut message_buf = BytesMut::with_capacity(BINCODE_BUFFER_SIZE);
+
+    bincode::encode_into_slice(message, &mut message_buf, *BINCODE_CONFIG)?;
+
+    Ok(message_buf.into())
+}
+
+pub fn decode_message<M: Decode>(data: Bytes) -> Result<M> {
+    let (res, _) = bincode::decode_from_slice(&data, *BINCODE_CONFIG)?;
+
+    Ok(res)
+}
+
+/// A TLS session ticket cache that persists its entries to a file, so resumption survives
+/// client restarts rather than just reconnects within the same process.
+///
+/// Falls back to an empty cache if the file does not exist or cannot be parsed.
+pub struct PersistentClientSessionCache {
+    path: PathBuf,
+    entries: Mutex<HashMap<Vec<u8>, Vec<u8>>>,
+}
+
+impl PersistentClientSessionCache {
+    /// Loads a persistent session cache from the given path, creating it on first use.
+    pub fn load(path: &Path) -> Arc<Self> {
+        let entries = fs::read(path)
+            .ok()
+            .and_then(|data| bincode::decode_from_slice(&data, *BINCODE_CONFIG).ok())
+            .map(|(entries, _)| entries)
+            .unwrap_or_default();
+
+        Arc::new(Self {
+            path: path.to_path_buf(),
+            entries: Mutex::new(entries),
+        })
+    }
+
+    /// Writes a snapshot of the cache to disk on a blocking-pool thread, so that callers on
+    /// the async runtime (e.g. rustls during the TLS handshake) never block on file I/O.
+    fn persist(&self, entries: HashMap<Vec<u8>, Vec<u8>>) {
+        let path = self.path.clone();
+
+        tokio::task::spawn_blocking(move || {
+            match bincode::encode_to_vec(&entries, *BINCODE_CONFIG) {
+                Ok(data) => {
+                    if let Err(e) = fs::write(&path, data) {
+                        warn!("Could not persist session cache to {path:?}: {e}");
+                    }
+                }
+                Err(e) => warn!("Could not encode session cache: {e}"),
+            }
+        });
+    }
+}
+
+impl StoresClientSessions for PersistentClientSessionCache {
+    fn put(&self, key: Vec<u8>, value: Vec<u8>) -> bool {
+        let entries = {
+            let mut entries = self.entries.lock().expect("session cache lock poisoned");
+            entries.insert(key, value);
+            entries.clone()
+        };
+        self.persist(entries);
+
+        true
+    }
+
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.entries
+            .lock()
+            .expect("session cache lock poisoned")
+            .get(key)
+            .cloned()
+    }
+}